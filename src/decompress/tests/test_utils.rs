@@ -61,6 +61,18 @@ pub fn decompress_by_chunks(
     decompress_impl(d, input, chunks, early_eof)
 }
 
+/// Decompresses `input` as a raw (headerless) DEFLATE stream, i.e. using
+/// `Decompressor::for_raw_deflate` instead of `Decompressor::new`.
+#[allow(dead_code)]
+pub fn decompress_raw_by_chunks(
+    input: &[u8],
+    chunks: impl IntoIterator<Item = usize>,
+    early_eof: bool,
+) -> Result<Vec<u8>, TestDecompressionError> {
+    let d = Decompressor::for_raw_deflate();
+    decompress_impl(d, input, chunks, early_eof)
+}
+
 /// Decompresses `input` using the specified `LITLEN_TABLE_SIZE` and `DIST_TABLE_SIZE`.
 #[allow(dead_code)]
 pub fn decompress_with_table_sizes<const LITLEN_TABLE_SIZE: usize, const DIST_TABLE_SIZE: usize>(
@@ -0,0 +1,176 @@
+//! Preset dictionary support for zlib streams (the `FDICT` mechanism).
+
+use crate::{DecompressionError, Decompressor};
+
+/// Size of the sliding window used to resolve DEFLATE back-references.
+const WINDOW_SIZE: usize = 32 * 1024;
+
+/// Computes the zlib dictionary identifier: the Adler-32 checksum of the dictionary bytes.
+fn dictionary_adler32(dict: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for &byte in dict {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Checks a zlib header's 4-byte `FDICT` dictionary identifier (`DICTID`) against the Adler-32 of
+/// the dictionary that was seeded via [`Decompressor::set_dictionary`].
+///
+/// `seeded_adler32` is `None` when `FDICT` is set but `set_dictionary` was never called.
+///
+/// TODO: the zlib header-parsing state machine (in `decompress.rs`) doesn't call this yet, so a
+/// stream with `FDICT` set is decoded without ever checking `DICTID` against the seeded
+/// dictionary. Wire this in once that state machine exposes a hook for it.
+#[allow(dead_code)]
+pub(crate) fn check_dictionary_id(
+    seeded_adler32: Option<u32>,
+    header_dictid: u32,
+    ignore_adler32: bool,
+) -> Result<(), DecompressionError> {
+    match seeded_adler32 {
+        None => Err(DecompressionError::MissingDictionary),
+        Some(actual) if ignore_adler32 || actual == header_dictid => Ok(()),
+        Some(actual) => Err(DecompressionError::DictionaryMismatch {
+            expected: header_dictid,
+            actual,
+        }),
+    }
+}
+
+impl Decompressor {
+    /// Seeds the 32 KiB sliding-window history with `dict`, so that back-references in the
+    /// compressed stream can resolve against it, and records its Adler-32 so it can later be
+    /// compared against the zlib header's `FDICT` dictionary identifier via
+    /// [`check_dictionary_id`].
+    ///
+    /// This must be called before the first call to [`Decompressor::read`]. If `dict` is larger
+    /// than the window, only its last 32 KiB are kept, matching how zlib itself ignores the
+    /// earlier part of an oversized dictionary.
+    ///
+    /// Note: `read()` does not yet call [`check_dictionary_id`] itself, so a header with `FDICT`
+    /// set is not currently rejected or validated automatically; that wiring is still a TODO in
+    /// the header-parsing state machine.
+    pub fn set_dictionary(&mut self, dict: &[u8]) {
+        let seed = if dict.len() > WINDOW_SIZE {
+            &dict[dict.len() - WINDOW_SIZE..]
+        } else {
+            dict
+        };
+        self.preload_window(seed);
+        self.set_dictionary_adler32(dictionary_adler32(dict));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_of_empty_dictionary_is_one() {
+        assert_eq!(dictionary_adler32(&[]), 1);
+    }
+
+    #[test]
+    fn adler32_matches_known_value() {
+        // Adler-32("Wikipedia") = 0x11E60398, a commonly cited test vector.
+        assert_eq!(dictionary_adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn missing_dictionary_is_rejected() {
+        assert_eq!(
+            check_dictionary_id(None, 0x1234_5678, false),
+            Err(DecompressionError::MissingDictionary)
+        );
+    }
+
+    #[test]
+    fn matching_dictionary_id_is_accepted() {
+        assert_eq!(check_dictionary_id(Some(42), 42, false), Ok(()));
+    }
+
+    #[test]
+    fn mismatched_dictionary_id_is_rejected() {
+        assert_eq!(
+            check_dictionary_id(Some(1), 2, false),
+            Err(DecompressionError::DictionaryMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn ignore_adler32_accepts_mismatch() {
+        assert_eq!(check_dictionary_id(Some(1), 2, true), Ok(()));
+    }
+
+    /// Appends `nbits` of `value` (LSB first) to `bytes`/`bit_len`, the way DEFLATE packs bits
+    /// within a byte stream.
+    fn push_bits(bytes: &mut Vec<u8>, bit_len: &mut usize, value: u32, nbits: u32) {
+        for i in 0..nbits {
+            let byte_idx = *bit_len / 8;
+            if byte_idx == bytes.len() {
+                bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                bytes[byte_idx] |= 1 << (*bit_len % 8);
+            }
+            *bit_len += 1;
+        }
+    }
+
+    /// Appends a fixed-Huffman-coded symbol: DEFLATE Huffman codes are conceptually MSB-first,
+    /// but packed into the bitstream like any other field (LSB first), so the code's bits are
+    /// reversed before pushing.
+    fn push_fixed_huffman_code(bytes: &mut Vec<u8>, bit_len: &mut usize, code: u32, nbits: u32) {
+        let mut reversed = 0u32;
+        for i in 0..nbits {
+            reversed |= ((code >> i) & 1) << (nbits - 1 - i);
+        }
+        push_bits(bytes, bit_len, reversed, nbits);
+    }
+
+    /// Builds a single final fixed-Huffman (`BTYPE=01`) raw DEFLATE block that emits one
+    /// length/distance back-reference (distance 1, i.e. "repeat the last output byte"), followed
+    /// by end-of-block.
+    fn fixed_huffman_backref_block(length: u32) -> Vec<u8> {
+        assert!((3..=10).contains(&length), "keeps the length code extra-bit-free");
+        let mut bytes = Vec::new();
+        let mut bit_len = 0;
+        push_bits(&mut bytes, &mut bit_len, 1, 1); // BFINAL
+        push_bits(&mut bytes, &mut bit_len, 0b01, 2); // BTYPE = fixed Huffman
+
+        // Length symbol: 257 + (length - 3), 7-bit fixed code in [256, 279] is `symbol - 256`.
+        let length_symbol = 257 + (length - 3);
+        push_fixed_huffman_code(&mut bytes, &mut bit_len, length_symbol - 256, 7);
+
+        // Distance symbol 0 (distance 1), 5-bit fixed code equal to the symbol itself.
+        push_fixed_huffman_code(&mut bytes, &mut bit_len, 0, 5);
+
+        // End-of-block symbol 256, 7-bit fixed code `0`.
+        push_fixed_huffman_code(&mut bytes, &mut bit_len, 0, 7);
+
+        bytes
+    }
+
+    #[test]
+    fn round_trips_a_back_reference_into_the_seeded_dictionary() {
+        let mut d = Decompressor::for_raw_deflate();
+        d.set_dictionary(b"ABCD");
+
+        let input = fixed_huffman_backref_block(4);
+        let mut out = [0u8; 4];
+        let (in_consumed, out_written) = d.read(&input, &mut out, 0, true).unwrap();
+        assert_eq!(in_consumed, input.len());
+        assert_eq!(out_written, 4);
+        // Distance 1 repeats the preceding byte; since the window is seeded with "ABCD", the
+        // first copy reaches back into the dictionary's last byte and each subsequent copy
+        // repeats the byte just emitted.
+        assert_eq!(&out, b"DDDD");
+        assert!(d.is_done());
+    }
+}
@@ -0,0 +1,482 @@
+//! gzip (RFC 1952) container support on top of the raw DEFLATE core.
+
+use alloc::vec::Vec;
+
+use crate::{DecompressionError, Decompressor};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const CM_DEFLATE: u8 = 8;
+
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+/// Upper bound on how many bytes a gzip header can plausibly span: the fixed 10-byte prefix, the
+/// largest possible `FEXTRA` field (a 2-byte length prefix maxing out at `u16::MAX`), and a
+/// generous allowance for `FNAME`/`FCOMMENT`/`FHCRC`.
+///
+/// Bounds how much of `read()`'s input is copied into `header_buf` while the header is still
+/// being assembled, so that decoding a whole file at once doesn't copy the entire compressed body
+/// into a buffer most of which is immediately discarded.
+const MAX_HEADER_LEN: usize = 10 + 2 + u16::MAX as usize + 4096;
+
+/// Errors that can occur while decoding a gzip container.
+#[derive(Debug, PartialEq)]
+pub enum GzipDecompressionError {
+    /// The 2-byte magic number at the start of the stream was not `\x1f\x8b`.
+    BadMagic,
+    /// The compression method byte was not `8` (DEFLATE).
+    UnsupportedMethod(u8),
+    /// The input ended in the middle of the header.
+    TruncatedHeader,
+    /// The header (typically its `FNAME`/`FCOMMENT` field) did not terminate within
+    /// [`MAX_HEADER_LEN`] bytes.
+    HeaderTooLarge,
+    /// The optional FHCRC header checksum did not match the header bytes.
+    BadHeaderCrc,
+    /// The input ended in the middle of the 8-byte trailer.
+    TruncatedTrailer,
+    /// The CRC-32 of the decompressed output did not match the trailer.
+    BadCrc32 { expected: u32, actual: u32 },
+    /// The trailer's ISIZE (uncompressed length mod 2^32) did not match.
+    BadIsize { expected: u32, actual: u32 },
+    /// An error occurred in the underlying DEFLATE decoder.
+    Deflate(DecompressionError),
+}
+
+impl From<DecompressionError> for GzipDecompressionError {
+    fn from(e: DecompressionError) -> Self {
+        Self::Deflate(e)
+    }
+}
+
+impl core::fmt::Display for GzipDecompressionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a gzip stream (bad magic number)"),
+            Self::UnsupportedMethod(method) => {
+                write!(f, "unsupported gzip compression method {method}")
+            }
+            Self::TruncatedHeader => write!(f, "truncated gzip header"),
+            Self::HeaderTooLarge => write!(f, "gzip header exceeds {MAX_HEADER_LEN} bytes"),
+            Self::BadHeaderCrc => write!(f, "gzip header CRC16 (FHCRC) mismatch"),
+            Self::TruncatedTrailer => write!(f, "truncated gzip trailer"),
+            Self::BadCrc32 { expected, actual } => write!(
+                f,
+                "gzip trailer CRC-32 mismatch: expected {expected:#010x}, computed {actual:#010x}"
+            ),
+            Self::BadIsize { expected, actual } => write!(
+                f,
+                "gzip trailer ISIZE mismatch: expected {expected}, computed {actual}"
+            ),
+            Self::Deflate(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GzipDecompressionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Deflate(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum State {
+    Header,
+    Body,
+    Trailer,
+    Done,
+}
+
+/// Computes a running (un-finalized) CRC-32 over `bytes`, continuing from `state`.
+///
+/// `state` should start as `u32::MAX`; call `!state` once all input has been fed through to get
+/// the finished CRC-32 value.
+fn crc32_update(state: u32, bytes: &[u8]) -> u32 {
+    let mut crc = state;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    crc
+}
+
+/// Tries to parse the gzip header at the start of `buf`. Returns `Ok(None)` if `buf` doesn't yet
+/// contain the whole header.
+fn try_parse_header(buf: &[u8]) -> Result<Option<usize>, GzipDecompressionError> {
+    if buf.len() < 10 {
+        return Ok(None);
+    }
+    if buf[0..2] != GZIP_MAGIC {
+        return Err(GzipDecompressionError::BadMagic);
+    }
+    if buf[2] != CM_DEFLATE {
+        return Err(GzipDecompressionError::UnsupportedMethod(buf[2]));
+    }
+    let flags = buf[3];
+    let mut pos = 10;
+
+    if flags & FEXTRA != 0 {
+        if buf.len() < pos + 2 {
+            return Ok(None);
+        }
+        let xlen = u16::from_le_bytes([buf[pos], buf[pos + 1]]) as usize;
+        pos += 2;
+        if buf.len() < pos + xlen {
+            return Ok(None);
+        }
+        pos += xlen;
+    }
+
+    if flags & FNAME != 0 {
+        match buf[pos..].iter().position(|&b| b == 0) {
+            Some(nul) => pos += nul + 1,
+            None => return Ok(None),
+        }
+    }
+
+    if flags & FCOMMENT != 0 {
+        match buf[pos..].iter().position(|&b| b == 0) {
+            Some(nul) => pos += nul + 1,
+            None => return Ok(None),
+        }
+    }
+
+    if flags & FHCRC != 0 {
+        if buf.len() < pos + 2 {
+            return Ok(None);
+        }
+        let header_crc16 = u16::from_le_bytes([buf[pos], buf[pos + 1]]);
+        let actual_crc16 = !crc32_update(u32::MAX, &buf[..pos]) as u16;
+        pos += 2;
+        if header_crc16 != actual_crc16 {
+            return Err(GzipDecompressionError::BadHeaderCrc);
+        }
+    }
+
+    Ok(Some(pos))
+}
+
+/// Validates an 8-byte gzip trailer against the CRC-32/length computed while decoding the body.
+fn check_trailer(
+    trailer: &[u8; 8],
+    computed_crc32: u32,
+    computed_out_len: u64,
+    ignore_crc32: bool,
+) -> Result<(), GzipDecompressionError> {
+    let crc32_trailer = u32::from_le_bytes(trailer[0..4].try_into().unwrap());
+    let isize_trailer = u32::from_le_bytes(trailer[4..8].try_into().unwrap());
+
+    if !ignore_crc32 && crc32_trailer != computed_crc32 {
+        return Err(GzipDecompressionError::BadCrc32 {
+            expected: crc32_trailer,
+            actual: computed_crc32,
+        });
+    }
+
+    let actual_isize = (computed_out_len & 0xFFFF_FFFF) as u32;
+    if isize_trailer != actual_isize {
+        return Err(GzipDecompressionError::BadIsize {
+            expected: isize_trailer,
+            actual: actual_isize,
+        });
+    }
+
+    Ok(())
+}
+
+/// Decodes a gzip-wrapped DEFLATE stream using the same fast inflate core as [`Decompressor`].
+pub struct GzipDecompressor {
+    decompressor: Decompressor,
+    state: State,
+    header_buf: Vec<u8>,
+    trailer_buf: Vec<u8>,
+    crc32: u32,
+    out_len: u64,
+    ignore_crc32: bool,
+}
+
+impl GzipDecompressor {
+    /// Creates a new gzip decompressor.
+    pub fn new() -> Self {
+        Self {
+            decompressor: Decompressor::for_raw_deflate(),
+            state: State::Header,
+            header_buf: Vec::new(),
+            trailer_buf: Vec::new(),
+            crc32: u32::MAX,
+            out_len: 0,
+            ignore_crc32: false,
+        }
+    }
+
+    /// Disables trailer CRC-32 validation, mirroring [`Decompressor::ignore_adler32`] so fuzzers
+    /// can explore inputs without checksum gating.
+    pub fn ignore_crc32(&mut self) {
+        self.ignore_crc32 = true;
+    }
+
+    /// Returns whether the entire gzip container (header, body and trailer) has been decoded.
+    pub fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    /// Decodes as much of `input` as possible, writing decompressed bytes into
+    /// `output[output_position..]`.
+    ///
+    /// Returns `(bytes_consumed_from_input, bytes_written_to_output)`, following the same
+    /// conventions as [`Decompressor::read`].
+    pub fn read(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+        output_position: usize,
+        end_of_input: bool,
+    ) -> Result<(usize, usize), GzipDecompressionError> {
+        let mut in_pos = 0;
+        let mut out_pos = output_position;
+
+        if self.state == State::Header {
+            let prev_len = self.header_buf.len();
+            let take = input.len().min(MAX_HEADER_LEN.saturating_sub(prev_len));
+            self.header_buf.extend_from_slice(&input[..take]);
+            match try_parse_header(&self.header_buf)? {
+                None => {
+                    return if end_of_input {
+                        Err(GzipDecompressionError::TruncatedHeader)
+                    } else if self.header_buf.len() >= MAX_HEADER_LEN {
+                        Err(GzipDecompressionError::HeaderTooLarge)
+                    } else {
+                        Ok((take, 0))
+                    };
+                }
+                Some(header_len) => {
+                    in_pos = header_len.saturating_sub(prev_len);
+                    self.header_buf.truncate(header_len);
+                    self.state = State::Body;
+                }
+            }
+        }
+
+        if self.state == State::Body {
+            let (body_consumed, out_written) =
+                self.decompressor
+                    .read(&input[in_pos..], output, out_pos, end_of_input)?;
+            in_pos += body_consumed;
+            self.crc32 = crc32_update(self.crc32, &output[out_pos..out_pos + out_written]);
+            self.out_len += out_written as u64;
+            out_pos += out_written;
+
+            if self.decompressor.is_done() {
+                self.state = State::Trailer;
+            } else {
+                return Ok((in_pos, out_pos - output_position));
+            }
+        }
+
+        if self.state == State::Trailer {
+            let remaining = &input[in_pos..];
+            let need = 8 - self.trailer_buf.len();
+            let take = need.min(remaining.len());
+            self.trailer_buf.extend_from_slice(&remaining[..take]);
+            in_pos += take;
+
+            if self.trailer_buf.len() == 8 {
+                let trailer: [u8; 8] = self.trailer_buf[..].try_into().unwrap();
+                check_trailer(&trailer, !self.crc32, self.out_len, self.ignore_crc32)?;
+                self.state = State::Done;
+            } else if end_of_input {
+                return Err(GzipDecompressionError::TruncatedTrailer);
+            }
+        }
+
+        Ok((in_pos, out_pos - output_position))
+    }
+}
+
+impl Default for GzipDecompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_minimal_header() {
+        let header = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+        assert_eq!(try_parse_header(&header), Ok(Some(10)));
+    }
+
+    #[test]
+    fn requests_more_bytes_for_incomplete_header() {
+        let header = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0];
+        assert_eq!(try_parse_header(&header), Ok(None));
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let header = [0x00, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+        assert_eq!(
+            try_parse_header(&header),
+            Err(GzipDecompressionError::BadMagic)
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_method() {
+        let header = [0x1f, 0x8b, 9, 0, 0, 0, 0, 0, 0, 0xff];
+        assert_eq!(
+            try_parse_header(&header),
+            Err(GzipDecompressionError::UnsupportedMethod(9))
+        );
+    }
+
+    #[test]
+    fn parses_fname_and_fcomment() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FNAME | FCOMMENT, 0, 0, 0, 0, 0, 0xff]);
+        header.extend_from_slice(b"name.txt\0");
+        header.extend_from_slice(b"a comment\0");
+        assert_eq!(try_parse_header(&header), Ok(Some(header.len())));
+    }
+
+    #[test]
+    fn fname_without_terminator_requests_more_bytes() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FNAME, 0, 0, 0, 0, 0, 0xff]);
+        header.extend_from_slice(b"no-nul-yet");
+        assert_eq!(try_parse_header(&header), Ok(None));
+    }
+
+    #[test]
+    fn parses_fextra() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FEXTRA, 0, 0, 0, 0, 0, 0xff]);
+        header.extend_from_slice(&3u16.to_le_bytes());
+        header.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(try_parse_header(&header), Ok(Some(header.len())));
+    }
+
+    #[test]
+    fn validates_correct_fhcrc() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FHCRC, 0, 0, 0, 0, 0, 0xff]);
+        let crc16 = !crc32_update(u32::MAX, &header) as u16;
+        header.extend_from_slice(&crc16.to_le_bytes());
+        assert_eq!(try_parse_header(&header), Ok(Some(header.len())));
+    }
+
+    #[test]
+    fn rejects_bad_fhcrc() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FHCRC, 0, 0, 0, 0, 0, 0xff]);
+        header.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            try_parse_header(&header),
+            Err(GzipDecompressionError::BadHeaderCrc)
+        );
+    }
+
+    #[test]
+    fn accepts_matching_trailer() {
+        let crc = !crc32_update(u32::MAX, b"hi");
+        let mut trailer = Vec::new();
+        trailer.extend_from_slice(&crc.to_le_bytes());
+        trailer.extend_from_slice(&2u32.to_le_bytes());
+        let trailer: [u8; 8] = trailer.try_into().unwrap();
+        assert_eq!(check_trailer(&trailer, crc, 2, false), Ok(()));
+    }
+
+    #[test]
+    fn rejects_crc32_mismatch() {
+        let mut trailer = Vec::new();
+        trailer.extend_from_slice(&0u32.to_le_bytes());
+        trailer.extend_from_slice(&2u32.to_le_bytes());
+        let trailer: [u8; 8] = trailer.try_into().unwrap();
+        let actual = !crc32_update(u32::MAX, b"hi");
+        assert_eq!(
+            check_trailer(&trailer, actual, 2, false),
+            Err(GzipDecompressionError::BadCrc32 {
+                expected: 0,
+                actual
+            })
+        );
+    }
+
+    #[test]
+    fn ignore_crc32_skips_crc_check_but_not_isize() {
+        let mut trailer = Vec::new();
+        trailer.extend_from_slice(&0u32.to_le_bytes());
+        trailer.extend_from_slice(&2u32.to_le_bytes());
+        let trailer: [u8; 8] = trailer.try_into().unwrap();
+        let actual = !crc32_update(u32::MAX, b"hi");
+        assert_eq!(check_trailer(&trailer, actual, 2, true), Ok(()));
+        assert_eq!(
+            check_trailer(&trailer, actual, 3, true),
+            Err(GzipDecompressionError::BadIsize {
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+
+    /// Builds a full gzip member wrapping `payload` in a single uncompressed ("stored") DEFLATE
+    /// block, so it can be used as a decoder fixture without a real compressor.
+    fn gzip_member(payload: &[u8]) -> Vec<u8> {
+        let mut v = Vec::from([0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff]);
+        v.extend_from_slice(&crate::test_fixtures::stored_deflate_block(payload));
+        let crc = !crc32_update(u32::MAX, payload);
+        v.extend_from_slice(&crc.to_le_bytes());
+        v.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+        v
+    }
+
+    #[test]
+    fn round_trips_a_minimal_gzip_member() {
+        let member = gzip_member(b"hello, gzip");
+        let mut d = GzipDecompressor::new();
+        let mut out = vec![0u8; 1024];
+        let mut in_pos = 0;
+        let mut out_pos = 0;
+        while !d.is_done() {
+            let (consumed, written) = d
+                .read(&member[in_pos..], &mut out, out_pos, true)
+                .unwrap();
+            in_pos += consumed;
+            out_pos += written;
+        }
+        assert_eq!(&out[..out_pos], b"hello, gzip");
+    }
+
+    #[test]
+    fn header_buf_is_truncated_to_the_header() {
+        let mut member = gzip_member(b"hello, gzip");
+        member.extend_from_slice(&[0xAA; 1024]); // trailing garbage past the gzip member
+        let mut d = GzipDecompressor::new();
+        let mut out = vec![0u8; 1024];
+        // `read` doesn't stop at the header boundary: once it's parsed, the same call falls
+        // through into decoding as much of the body as `input`/`output` allow. While the header
+        // is being parsed, `header_buf` (the scratch buffer `try_parse_header` parses out of)
+        // receives up to `MAX_HEADER_LEN` bytes of `input`, here the whole member plus the
+        // trailing garbage. What this test guards is that once the header is parsed,
+        // `header_buf` is cut back to just the 10-byte fixed header.
+        d.read(&member, &mut out, 0, false).unwrap();
+        assert_eq!(d.header_buf.len(), 10);
+    }
+
+    #[test]
+    fn oversized_header_is_rejected() {
+        let mut header = Vec::from([0x1f, 0x8b, 8, FNAME, 0, 0, 0, 0, 0, 0xff]);
+        header.extend(core::iter::repeat(b'a').take(MAX_HEADER_LEN));
+        let mut d = GzipDecompressor::new();
+        let mut out = vec![0u8; 16];
+        let err = d.read(&header, &mut out, 0, false).unwrap_err();
+        assert_eq!(err, GzipDecompressionError::HeaderTooLarge);
+    }
+}
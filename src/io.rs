@@ -0,0 +1,222 @@
+//! [`std::io::Read`] and [`std::io::BufRead`] adaptors wrapping [`Decompressor`].
+//!
+//! These mirror the stream adaptors flate2 provides over its raw `Decompress` type, so callers
+//! can decode a zlib stream without hand-rolling the `Decompressor::read` loop.
+
+use std::io::{self, BufRead, Read};
+
+use crate::{DecompressionError, Decompressor};
+
+const INPUT_BUFFER_SIZE: usize = 32 * 1024;
+
+fn decompression_error_to_io_error(e: DecompressionError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// Wraps a [`std::io::Read`] of compressed bytes, exposing the decompressed stream through
+/// `std::io::Read`.
+pub struct ReadDecompressor<R> {
+    inner: R,
+    decompressor: Decompressor,
+    buf: Vec<u8>,
+    buf_pos: usize,
+    buf_len: usize,
+    inner_eof: bool,
+}
+
+impl<R: Read> ReadDecompressor<R> {
+    /// Wraps `inner`, decoding a zlib stream read from it.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            decompressor: Decompressor::new(),
+            buf: vec![0; INPUT_BUFFER_SIZE],
+            buf_pos: 0,
+            buf_len: 0,
+            inner_eof: false,
+        }
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes `self`, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns bytes that were read from the inner reader but not yet consumed by the
+    /// decompressor, e.g. trailing bytes that follow the zlib stream in a larger container.
+    pub fn take_unread(&mut self) -> Vec<u8> {
+        let unread = self.buf[self.buf_pos..self.buf_len].to_vec();
+        self.buf_pos = self.buf_len;
+        unread
+    }
+
+    fn fill_buf_if_needed(&mut self) -> io::Result<()> {
+        if self.buf_pos == self.buf_len && !self.inner_eof {
+            self.buf_len = self.inner.read(&mut self.buf)?;
+            self.buf_pos = 0;
+            if self.buf_len == 0 {
+                self.inner_eof = true;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for ReadDecompressor<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.decompressor.is_done() {
+            return Ok(0);
+        }
+
+        loop {
+            self.fill_buf_if_needed()?;
+
+            let input = &self.buf[self.buf_pos..self.buf_len];
+            let (in_consumed, out_written) = self
+                .decompressor
+                .read(input, out, 0, self.inner_eof)
+                .map_err(decompression_error_to_io_error)?;
+            self.buf_pos += in_consumed;
+
+            if out_written > 0 || self.decompressor.is_done() {
+                return Ok(out_written);
+            }
+            if self.inner_eof && in_consumed == 0 {
+                return Ok(0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod read_decompressor_tests {
+    use super::*;
+    use crate::test_fixtures::stored_block_zlib;
+    use std::io::Cursor;
+
+    #[test]
+    fn decodes_whole_stream() {
+        let data = stored_block_zlib(b"hello, world");
+        let mut r = ReadDecompressor::new(Cursor::new(data));
+        let mut out = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"hello, world");
+    }
+
+    #[test]
+    fn recovers_trailing_unread_bytes() {
+        let mut data = stored_block_zlib(b"hello, world");
+        data.extend_from_slice(b"TRAILING");
+        let mut r = ReadDecompressor::new(Cursor::new(data));
+        let mut out = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"hello, world");
+        assert_eq!(r.take_unread(), b"TRAILING");
+    }
+
+    #[test]
+    fn decodes_with_tiny_output_buffer() {
+        let data = stored_block_zlib(b"hello, world");
+        let mut r = ReadDecompressor::new(Cursor::new(data));
+        let mut out = Vec::new();
+        let mut chunk = [0u8; 3];
+        loop {
+            let n = r.read(&mut chunk).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(out, b"hello, world");
+    }
+
+    #[test]
+    fn surfaces_bad_stream_as_invalid_data_error() {
+        let mut r = ReadDecompressor::new(Cursor::new(vec![0xff, 0xff, 0xff, 0xff]));
+        let mut out = Vec::new();
+        let err = r.read_to_end(&mut out).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
+
+/// Like [`ReadDecompressor`], but reads compressed input directly from an inner
+/// [`std::io::BufRead`] instead of maintaining its own input buffer.
+///
+/// Since the inner reader already exposes its buffer via `fill_buf`/`consume`, there is no
+/// separate "unread bytes" to recover: trailing bytes simply remain available by reading
+/// `into_inner()` further.
+pub struct BufReadDecompressor<R> {
+    inner: R,
+    decompressor: Decompressor,
+    inner_eof: bool,
+}
+
+impl<R: BufRead> BufReadDecompressor<R> {
+    /// Wraps `inner`, decoding a zlib stream read from it.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            decompressor: Decompressor::new(),
+            inner_eof: false,
+        }
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes `self`, returning the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for BufReadDecompressor<R> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.decompressor.is_done() {
+            return Ok(0);
+        }
+
+        loop {
+            let input = self.inner.fill_buf()?;
+            if input.is_empty() {
+                self.inner_eof = true;
+            }
+
+            let (in_consumed, out_written) = self
+                .decompressor
+                .read(input, out, 0, self.inner_eof)
+                .map_err(decompression_error_to_io_error)?;
+            self.inner.consume(in_consumed);
+
+            if out_written > 0 || self.decompressor.is_done() {
+                return Ok(out_written);
+            }
+            if self.inner_eof && in_consumed == 0 {
+                return Ok(0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod bufread_decompressor_tests {
+    use super::*;
+    use crate::test_fixtures::stored_block_zlib;
+    use std::io::Cursor;
+
+    #[test]
+    fn decodes_whole_stream() {
+        let data = stored_block_zlib(b"buffered stream");
+        let mut r = BufReadDecompressor::new(Cursor::new(data));
+        let mut out = Vec::new();
+        r.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"buffered stream");
+    }
+}
@@ -0,0 +1,41 @@
+//! A fast DEFLATE and zlib decompressor, tuned for decoding PNG `IDAT` streams.
+//!
+//! See [`Decompressor`] for the main entry point into the crate's streaming, allocation-free
+//! decoding API.
+//!
+//! The crate is `no_std` (but not allocation-free): disable the default `std` feature to build
+//! against `core` + `alloc` only. The I/O adaptors in [`ReadDecompressor`]/[`BufReadDecompressor`]
+//! require `std` and are unavailable in that configuration. The async [`DecompressStream`]
+//! adaptor additionally requires the `futures` feature (off by default), since it depends on
+//! `futures-core`/`futures`.
+//!
+//! TODO: there is no `Cargo.toml` in this checkout at all yet, so the `std`/`futures` features and
+//! the `futures-core`/`futures` dependencies these `cfg`s assume aren't declared anywhere; until a
+//! manifest exists, `std`/`futures` can't actually be toggled by a real build. Adding one is out of
+//! scope for this change, since `mod decompress` below also has no backing source file yet and a
+//! manifest alone wouldn't make the crate buildable.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod decompress;
+mod dictionary;
+mod gzip;
+#[cfg(feature = "std")]
+mod io;
+mod raw;
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(test)]
+mod test_fixtures;
+
+// TODO: `DecompressionError`'s `std::error::Error` impl should be gated behind
+// `#[cfg(feature = "std")]`, the way `GzipDecompressionError`'s is. It isn't, because
+// `decompress.rs` itself (the module declared below) has no backing source file in this checkout
+// yet; that impl lands whenever the module does, not as a standalone change here.
+pub use decompress::{DecompressedRead, DecompressionError, Decompressor, GenericDecompressor};
+pub use gzip::{GzipDecompressionError, GzipDecompressor};
+#[cfg(feature = "std")]
+pub use io::{BufReadDecompressor, ReadDecompressor};
+#[cfg(feature = "futures")]
+pub use stream::DecompressStream;
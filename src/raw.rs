@@ -0,0 +1,21 @@
+//! Raw DEFLATE (RFC 1951) decoding, bypassing the zlib header and trailing Adler-32.
+
+use crate::GenericDecompressor;
+
+impl<const LITLEN_TABLE_SIZE: usize, const DIST_TABLE_SIZE: usize>
+    GenericDecompressor<LITLEN_TABLE_SIZE, DIST_TABLE_SIZE>
+{
+    /// Creates a decompressor for a bare RFC 1951 DEFLATE stream, skipping the 2-byte zlib
+    /// header and the trailing Adler-32 checksum.
+    ///
+    /// This is useful for DEFLATE blocks that are framed by an outer container (e.g. zip
+    /// entries) rather than by zlib, mirroring the `DeflateDecoder`/`ZlibDecoder` split other
+    /// crates provide. `Decompressor::for_raw_deflate()` resolves here since `Decompressor` is
+    /// just a type alias for a particular `GenericDecompressor` instantiation.
+    pub fn for_raw_deflate() -> Self {
+        let mut d = Self::new();
+        d.skip_zlib_header();
+        d.ignore_adler32();
+        d
+    }
+}
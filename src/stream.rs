@@ -0,0 +1,197 @@
+//! An async adapter that turns a [`Stream`] of compressed input chunks into a [`FusedStream`] of
+//! decompressed output chunks.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::{FusedStream, Stream};
+
+use crate::{DecompressionError, Decompressor};
+
+/// Default size (in bytes) of the output chunks yielded by [`DecompressStream`].
+const DEFAULT_OUTPUT_CHUNK_SIZE: usize = 32 * 1024;
+
+/// Adapts a `Stream` of compressed input chunks into a `FusedStream` of decompressed output
+/// chunks, so fdeflate can be dropped into async network pipelines instead of forcing callers to
+/// hand-roll the `Decompressor::read` loop.
+///
+/// Once decoding fails with a [`DecompressionError`], `DecompressStream` yields that error
+/// exactly once and then terminates: every later `poll_next` returns `None`.
+pub struct DecompressStream<S> {
+    inner: S,
+    decompressor: Decompressor,
+    output_chunk_size: usize,
+    input_buf: Vec<u8>,
+    input_pos: usize,
+    input_done: bool,
+    done: bool,
+}
+
+impl<S> DecompressStream<S>
+where
+    S: Stream<Item = Vec<u8>> + Unpin,
+{
+    /// Wraps `inner`, yielding output chunks of the default size (32 KiB).
+    pub fn new(inner: S) -> Self {
+        Self::with_output_chunk_size(inner, DEFAULT_OUTPUT_CHUNK_SIZE)
+    }
+
+    /// Wraps `inner`, yielding output chunks of up to `output_chunk_size` bytes.
+    ///
+    /// Panics if `output_chunk_size` is zero.
+    pub fn with_output_chunk_size(inner: S, output_chunk_size: usize) -> Self {
+        assert!(output_chunk_size > 0);
+        Self {
+            inner,
+            decompressor: Decompressor::new(),
+            output_chunk_size,
+            input_buf: Vec::new(),
+            input_pos: 0,
+            input_done: false,
+            done: false,
+        }
+    }
+
+    /// Consumes `self`, returning the wrapped input stream.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> Stream for DecompressStream<S>
+where
+    S: Stream<Item = Vec<u8>> + Unpin,
+{
+    type Item = Result<Vec<u8>, DecompressionError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut out_buf = vec![0u8; this.output_chunk_size];
+        let mut out_pos = 0;
+
+        while out_pos < out_buf.len() && !this.decompressor.is_done() {
+            if this.input_pos == this.input_buf.len() && !this.input_done {
+                match Pin::new(&mut this.inner).poll_next(cx) {
+                    Poll::Ready(Some(chunk)) => {
+                        this.input_buf = chunk;
+                        this.input_pos = 0;
+                    }
+                    Poll::Ready(None) => this.input_done = true,
+                    Poll::Pending => {
+                        return if out_pos > 0 {
+                            out_buf.truncate(out_pos);
+                            Poll::Ready(Some(Ok(out_buf)))
+                        } else {
+                            Poll::Pending
+                        };
+                    }
+                }
+                continue;
+            }
+
+            let input = &this.input_buf[this.input_pos..];
+            match this
+                .decompressor
+                .read(input, &mut out_buf, out_pos, this.input_done)
+            {
+                Ok((in_consumed, out_written)) => {
+                    this.input_pos += in_consumed;
+                    out_pos += out_written;
+                    if this.input_done && in_consumed == 0 && out_written == 0 {
+                        // No more progress is possible even though `is_done()` isn't set yet,
+                        // i.e. the input was truncated. Surface this as an error instead of
+                        // silently treating it as a clean end of stream, and discard whatever
+                        // partial chunk was accumulated so far.
+                        this.done = true;
+                        return Poll::Ready(Some(Err(DecompressionError::UnexpectedEof)));
+                    }
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+
+        if this.decompressor.is_done() {
+            this.done = true;
+        }
+
+        if out_pos == 0 {
+            return Poll::Ready(None);
+        }
+
+        out_buf.truncate(out_pos);
+        Poll::Ready(Some(Ok(out_buf)))
+    }
+}
+
+impl<S> FusedStream for DecompressStream<S>
+where
+    S: Stream<Item = Vec<u8>> + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::stored_block_zlib;
+    use futures::StreamExt;
+
+    #[test]
+    fn decodes_a_single_input_chunk() {
+        let compressed = stored_block_zlib(b"hello, world");
+        let input = futures::stream::iter(vec![compressed]);
+        let chunks: Vec<_> =
+            futures::executor::block_on(DecompressStream::new(input).collect());
+        let output: Vec<u8> = chunks
+            .into_iter()
+            .collect::<Result<Vec<Vec<u8>>, _>>()
+            .unwrap()
+            .concat();
+        assert_eq!(output, b"hello, world");
+    }
+
+    #[test]
+    fn decodes_input_split_across_several_chunks() {
+        let compressed = stored_block_zlib(b"hello, world");
+        let input = futures::stream::iter(compressed.into_iter().map(|b| vec![b]));
+        let chunks: Vec<_> =
+            futures::executor::block_on(DecompressStream::new(input).collect());
+        let output: Vec<u8> = chunks
+            .into_iter()
+            .collect::<Result<Vec<Vec<u8>>, _>>()
+            .unwrap()
+            .concat();
+        assert_eq!(output, b"hello, world");
+    }
+
+    #[test]
+    fn truncated_input_yields_an_error_and_then_terminates() {
+        let mut compressed = stored_block_zlib(b"hello, world");
+        compressed.truncate(compressed.len() - 3);
+        let input = futures::stream::iter(vec![compressed]);
+        let mut stream = DecompressStream::new(input);
+
+        let results: Vec<_> = futures::executor::block_on(async {
+            let mut out = Vec::new();
+            while let Some(item) = stream.next().await {
+                out.push(item);
+            }
+            out
+        });
+
+        assert!(matches!(results.last(), Some(Err(DecompressionError::UnexpectedEof))));
+        assert!(stream.is_terminated());
+    }
+}
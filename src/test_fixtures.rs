@@ -0,0 +1,41 @@
+//! Shared hand-rolled DEFLATE/zlib fixtures for the unit tests in `stream`, `io`, and `gzip`.
+//!
+//! Those modules each need a minimal compressed-data fixture to exercise their decoding without
+//! pulling in a real compressor; this centralizes that fixture-building logic in one place
+//! instead of re-deriving it per module.
+#![cfg(test)]
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Computes the Adler-32 checksum of `bytes`, as used in a zlib stream's trailer.
+pub(crate) fn adler32(bytes: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+/// Encodes `payload` as a single final, uncompressed ("stored") DEFLATE block: a 1-byte block
+/// header (`BFINAL=1`, `BTYPE=00`), a little-endian `LEN`/`~LEN` pair, then the raw bytes.
+pub(crate) fn stored_deflate_block(payload: &[u8]) -> Vec<u8> {
+    let mut v = vec![0x01];
+    let len = payload.len() as u16;
+    v.extend_from_slice(&len.to_le_bytes());
+    v.extend_from_slice(&(!len).to_le_bytes());
+    v.extend_from_slice(payload);
+    v
+}
+
+/// Wraps `payload` in a minimal zlib stream (a `0x78 0x01` CMF/FLG header, a single stored
+/// DEFLATE block, and an Adler-32 trailer), so it can be used as a decoder fixture without a
+/// real compressor.
+pub(crate) fn stored_block_zlib(payload: &[u8]) -> Vec<u8> {
+    let mut v = vec![0x78, 0x01];
+    v.extend_from_slice(&stored_deflate_block(payload));
+    v.extend_from_slice(&adler32(payload).to_be_bytes());
+    v
+}